@@ -1,5 +1,5 @@
 extern crate clap;
-use clap::{App, Arg};
+use clap::{App, Arg, SubCommand};
 
 #[macro_use]
 extern crate error_chain;
@@ -17,6 +17,10 @@ error_chain! {
             description("Running the command failed")
             display("Running the command '{}' failed", c)
         }
+        EmptyCommand {
+            description("The command was empty")
+            display("Cannot run an empty command")
+        }
         ReadRemindersFileFailed
         ReadingInputFailed
     }
@@ -25,11 +29,11 @@ error_chain! {
 extern crate dirs;
 extern crate nix;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::ffi::CString;
 use std::fs::OpenOptions;
 use std::io::prelude::*;
-use std::iter::FromIterator;
+use std::process::{Command, Stdio};
 
 quick_main!(run);
 
@@ -38,157 +42,606 @@ fn run() -> Result<()> {
         .version("1.0")
         .author("Dean Johnson <dean@deanljohnson.com>")
         .about("Stores commands behind keywords and allows you to search for them later.")
-        .arg(
-            Arg::with_name("add")
-                .short("a")
-                .long("add")
-                .takes_value(true)
-                .value_names(&["command", "keywords"])
-                .help("Adds a command to your reminders with the given keywords."),
+        .subcommand(
+            SubCommand::with_name("add")
+                .about("Adds a command to your reminders with the given keywords.")
+                .arg(Arg::with_name("command").required(true))
+                .arg(Arg::with_name("keywords").required(true)),
+        )
+        .subcommand(
+            SubCommand::with_name("remove")
+                .about("Removes a command matching any of the given keywords.")
+                .arg(Arg::with_name("keywords").required(true)),
+        )
+        .subcommand(
+            SubCommand::with_name("search")
+                .about("Fuzzy searches your reminders, using the given keywords as the initial query.")
+                .arg(Arg::with_name("keywords").multiple(true)),
+        )
+        .subcommand(
+            SubCommand::with_name("edit")
+                .about("Opens the reminders file in $EDITOR for manual editing."),
+        )
+        .subcommand(
+            SubCommand::with_name("import")
+                .about("Imports a reminder set, merging it into the local store.")
+                .arg(Arg::with_name("path").help(
+                    "Path to the document to import. Reads stdin if omitted.",
+                )),
         )
-        .arg(
-            Arg::with_name("remove")
-                .short("r")
-                .long("remove")
-                .takes_value(true)
-                .value_name("keywords")
-                .help("Removes a command matching any of the given keywords."),
+        .subcommand(
+            SubCommand::with_name("export")
+                .about("Exports your reminders as a portable JSON document.")
+                .arg(Arg::with_name("path").help(
+                    "Path to write the document to. Writes stdout if omitted.",
+                )),
         )
-        .arg(Arg::with_name("search").multiple(true))
         .get_matches();
 
-    // Handle add command
-    if let Some(values) = matches.values_of("add") {
-        let mut values = values;
-        return do_add(values.next().unwrap(), values.next().unwrap());
+    match matches.subcommand() {
+        ("add", Some(sub)) => do_add(
+            sub.value_of("command").unwrap(),
+            sub.value_of("keywords").unwrap(),
+        ),
+        ("remove", Some(sub)) => do_remove(sub.value_of("keywords").unwrap()),
+        ("search", Some(sub)) => do_search(
+            sub.values_of("keywords")
+                .map(|v| v.collect::<Vec<&str>>())
+                .unwrap_or_else(Vec::new),
+        ),
+        ("edit", Some(_)) => do_edit(),
+        ("import", Some(sub)) => do_import(sub.value_of("path")),
+        ("export", Some(sub)) => do_export(sub.value_of("path")),
+        _ => Ok(()),
     }
-    // Handle remove command
-    if let Some(values) = matches.value_of("remove") {
-        return do_remove(values);
-    }
-    // Handle searching for keywords
-    if let Some(values) = matches.values_of("search") {
-        return do_search(values.collect::<Vec<&str>>());
-    }
-
-    Ok(())
 }
 
-/// Handles the command "--add [command] [keywords]".
+/// Handles the "add" subcommand.
 /// Will either add the command to the reminders file
 /// or ask the user if they want to merge these keywords
 /// with any other keywords already existing for the command.
 fn do_add(command: &str, keywords: &str) -> Result<()> {
-    let data = read_reminders_file().chain_err(|| ErrorKind::ReadRemindersFileFailed)?;
-
     if command.trim().is_empty() {
         return Err(ErrorKind::AddFailed("Command was empty").into());
     }
 
-    let mut line_index: usize = 0;
-    for line in data.lines() {
-        if line == command {
-            return add_to_preexisting_command(&data, command, keywords, line_index);
-        }
-        line_index = line_index + 1;
-    }
+    let reminders = read_reminders()?;
+    return add_or_merge_command(reminders, command, split_keywords(keywords));
+}
 
-    return add_new_command(&data, command, keywords);
+/// Adds `command` to `reminders`, or - if a reminder for it already exists -
+/// asks the user whether to merge `keywords` into its existing keyword set.
+fn add_or_merge_command(
+    reminders: Vec<Reminder>,
+    command: &str,
+    keywords: HashSet<String>,
+) -> Result<()> {
+    match reminders.iter().position(|r| r.command == command) {
+        Some(idx) => add_to_preexisting_command(reminders, idx, keywords),
+        None => add_new_command(reminders, command, keywords),
+    }
 }
 
-/// Handles removing commands for a given keyword.
-/// Will ask the user before removing each command.
+/// Handles the "remove" subcommand.
+/// Will ask the user before removing each matching reminder.
 fn do_remove(keywords: &str) -> Result<()> {
-    // TODO: what happens if keywords has a "#"?
-    let data = read_reminders_file().chain_err(|| ErrorKind::ReadRemindersFileFailed)?;
-    let data_lines = data.lines().collect();
-    let keywords_vec = keywords.split(" ").collect();
+    let mut reminders = read_reminders()?;
+    let keywords = split_keywords(keywords);
+    let matching_indices = find_matching_reminders(&reminders, &keywords);
 
-    let matching_indices = find_matching_commands(&data_lines, &keywords_vec);
-    let removed_indices = {
-        let remove_command_filter =
-            |l: &&usize| match ask_yes_no(&format!("Remove \"{}\"? (y/n) ", data_lines[**l])) {
+    let mut removed_indices = matching_indices
+        .into_iter()
+        .filter(|idx| {
+            match ask_yes_no(&format!("Remove \"{}\"? (y/n) ", reminders[*idx].command)) {
                 Err(_) => true,
                 Ok(v) => v,
-            };
-
-        let mut cmd_vec = matching_indices
-            .iter()
-            .filter(remove_command_filter)
-            .collect::<Vec<&usize>>();
-        cmd_vec.reverse();
-        cmd_vec
-    };
+            }
+        })
+        .collect::<Vec<usize>>();
+    removed_indices.sort();
 
-    let mut data_lines = data_lines;
-    for cmd_line in removed_indices {
-        data_lines.remove(*cmd_line);
-        data_lines.remove(*cmd_line - 1);
+    for idx in removed_indices.into_iter().rev() {
+        reminders.remove(idx);
     }
 
-    return write_reminders_file(&data_lines.join("\n"));
+    return write_reminders(&reminders);
 }
 
-/// Handles the command "[keywords]".
-/// Will search for commands with any of the given keywords.
+/// Handles the "search" subcommand.
+/// Opens an interactive fuzzy finder over every stored reminder, with the
+/// given keywords joined by spaces as its initial query. The query is
+/// matched as a whole, so a reminder holding only one of several given
+/// keywords is not necessarily shown.
 fn do_search(keywords: Vec<&str>) -> Result<()> {
-    let data = read_reminders_file().chain_err(|| ErrorKind::ReadRemindersFileFailed)?;
-    let data_lines = data.lines().collect();
-    let cmd_vec = find_matching_commands(&data_lines, &keywords);
+    let reminders = read_reminders()?;
 
-    match cmd_vec.len() {
-        0 => println!("No commands found with any of the given keywords"),
-        1 => {
-            if ask_yes_no(&format!("Run '{}'? (y/n) ", data_lines[0]))
+    match fuzzy_search_commands(&reminders, &keywords)? {
+        None => {
+            println!("No command selected for the given keywords");
+            fetch_from_cheat_sh(&keywords)?;
+        }
+        Some(idx) => {
+            let command = reminders[idx].command.clone();
+            if ask_yes_no(&format!("Run '{}'? (y/n) ", command))
                 .chain_err(|| ErrorKind::ReadingInputFailed)?
             {
-                run_command(data_lines[0])
-                    .chain_err(|| ErrorKind::RunningCommandFailed(String::from(data_lines[0])))?;
+                run_command(&command)
+                    .chain_err(|| ErrorKind::RunningCommandFailed(command.clone()))?;
             }
-            return Ok(());
-        }
-        _ => {
-            let options = cmd_vec
-                .iter()
-                .map(|l| data_lines[*l])
-                .collect::<Vec<&str>>();
-            let cmd_number = ask_multiple(&options).chain_err(|| ErrorKind::ReadingInputFailed)?;
-            return run_command(options[cmd_number])
-                .chain_err(|| ErrorKind::RunningCommandFailed(String::from(options[cmd_number])));
         }
     }
 
     return Ok(());
 }
 
+/// Called when the local search selects no reminder (nothing matched, or
+/// the fuzzy finder was cancelled). Queries
+/// cheat.sh for the keywords, offers the returned command lines through
+/// `ask_multiple`, and - once the user picks one - offers to save it
+/// locally via `add_or_merge_command` before asking to run it, same as
+/// the local-match branch of `do_search`.
+fn fetch_from_cheat_sh(keywords: &Vec<&str>) -> Result<()> {
+    let keywords_str = keywords.join(" ");
+
+    if !ask_yes_no(&format!(
+        "Look up '{}' on cheat.sh? (y/n) ",
+        keywords_str
+    ))
+    .chain_err(|| ErrorKind::ReadingInputFailed)?
+    {
+        return Ok(());
+    }
+
+    let url = format!("cheat.sh/{}", keywords.join("+"));
+    let output = Command::new("curl")
+        .arg("-s")
+        .arg(&url)
+        .output()
+        .chain_err(|| format!("Fetching '{}' from cheat.sh failed", url))?;
+
+    let body = String::from_utf8_lossy(&output.stdout);
+    let candidates = body
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .collect::<Vec<&str>>();
+
+    if candidates.is_empty() {
+        println!("cheat.sh had no commands for '{}'", keywords_str);
+        return Ok(());
+    }
+
+    let selection = ask_multiple(&candidates).chain_err(|| ErrorKind::ReadingInputFailed)?;
+    let command = candidates[selection];
+
+    if ask_yes_no("Save this command locally? (y/n) ").chain_err(|| ErrorKind::ReadingInputFailed)? {
+        let reminders = read_reminders()?;
+        add_or_merge_command(reminders, command, split_keywords(&keywords_str))?;
+    }
+
+    if ask_yes_no(&format!("Run '{}'? (y/n) ", command)).chain_err(|| ErrorKind::ReadingInputFailed)? {
+        run_command(command)
+            .chain_err(|| ErrorKind::RunningCommandFailed(String::from(command)))?;
+    }
+
+    return Ok(());
+}
+
 /// Handles adding keywords to an already existing command reminder.
 fn add_to_preexisting_command(
-    data: &str,
-    command: &str,
-    keywords: &str,
-    command_line: usize,
+    mut reminders: Vec<Reminder>,
+    idx: usize,
+    keywords: HashSet<String>,
 ) -> Result<()> {
     if ask_yes_no("A reminder already exists for the given command. Merge keywords? (y/n) ")
         .chain_err(|| ErrorKind::ReadingInputFailed)?
     {
-        merge_keywords(data.as_ref(), command, keywords, command_line)
-            .chain_err(|| "Error merging keywords")?;
+        merge_keywords(&mut reminders[idx], keywords);
+        write_reminders(&reminders)?;
     }
     return Ok(());
 }
 
-/// Handles adding a new command reminder
-fn add_new_command(data: &str, command: &str, keywords: &str) -> Result<()> {
-    let new_data = format!("{}# {}\n{}", data, keywords, command);
-    return write_reminders_file(&new_data);
+/// Handles adding a new command reminder.
+fn add_new_command(
+    mut reminders: Vec<Reminder>,
+    command: &str,
+    keywords: HashSet<String>,
+) -> Result<()> {
+    reminders.push(Reminder {
+        keywords,
+        command: command.to_string(),
+    });
+    return write_reminders(&reminders);
 }
 
-/// Reads the reminders file into a string.
-fn read_reminders_file() -> Result<String> {
-    // Setup path to file
+/// Handles the "edit" subcommand. Opens the reminders file in the user's
+/// editor, waits for it to exit, then validates the result: every
+/// "# keywords" header line must be followed by a non-empty command line.
+fn do_edit() -> Result<()> {
+    let path = reminders_file_path();
+    let editor = std::env::var("EDITOR")
+        .or_else(|_| std::env::var("VISUAL"))
+        .unwrap_or_else(|_| String::from("vi"));
+
+    let status = Command::new(&editor)
+        .arg(&path)
+        .status()
+        .chain_err(|| format!("Launching editor '{}' failed", editor))?;
+
+    if !status.success() {
+        return Err(format!("Editor '{}' exited with a failure", editor).into());
+    }
+
+    let data = read_reminders_file().chain_err(|| ErrorKind::ReadRemindersFileFailed)?;
+    return validate_reminders(&data);
+}
+
+/// Validates that every "# keywords" header line in `data` is eventually
+/// followed by a non-empty command line (blank lines in between are fine,
+/// same as `parse_reminders`), returning an `AddFailed` error if any header
+/// is left dangling. Delegates to `parse_reminders` itself - rather than
+/// re-checking line adjacency - so this can't drift out of sync with what
+/// the rest of the tool actually accepts again.
+fn validate_reminders(data: &str) -> Result<()> {
+    let header_count = data.lines().filter(|line| line.starts_with('#')).count();
+    let parsed_count = parse_reminders(data).len();
+
+    if parsed_count < header_count {
+        return Err(ErrorKind::AddFailed(
+            "a '# keywords' line must be followed by a non-empty command",
+        )
+        .into());
+    }
+
+    return Ok(());
+}
+
+/// Handles the "export" subcommand. Serializes every reminder to a JSON
+/// document, writing it to `path` if given or to stdout otherwise.
+fn do_export(path: Option<&str>) -> Result<()> {
+    let json = serialize_reminders_json(&read_reminders()?);
+
+    return match path {
+        Some(p) => std::fs::write(p, json).chain_err(|| format!("Writing export to '{}' failed", p)),
+        None => {
+            println!("{}", json);
+            Ok(())
+        }
+    };
+}
+
+/// Handles the "import" subcommand. Reads a reminder set from `path` (or
+/// stdin if omitted) - either one of our own exported JSON documents or
+/// another user's reminders file - and merges it into the local store,
+/// reusing `merge_keywords` so commands that already exist just gain the
+/// imported keywords instead of producing duplicates.
+fn do_import(path: Option<&str>) -> Result<()> {
+    let raw = match path {
+        Some(p) => std::fs::read_to_string(p).chain_err(|| format!("Reading '{}' failed", p))?,
+        None => {
+            let mut buf = String::new();
+            std::io::stdin()
+                .read_to_string(&mut buf)
+                .chain_err(|| "Reading import document from stdin failed")?;
+            buf
+        }
+    };
+
+    let imported = if looks_like_json(&raw) {
+        parse_reminders_json(&raw)?
+    } else {
+        parse_reminders(&raw)
+    };
+
+    let mut reminders = read_reminders()?;
+    let mut added = 0;
+    let mut merged = 0;
+
+    for imported_reminder in imported {
+        match reminders
+            .iter()
+            .position(|r| r.command == imported_reminder.command)
+        {
+            Some(idx) => {
+                merge_keywords(&mut reminders[idx], imported_reminder.keywords);
+                merged += 1;
+            }
+            None => {
+                reminders.push(imported_reminder);
+                added += 1;
+            }
+        }
+    }
+
+    write_reminders(&reminders)?;
+
+    println!(
+        "Imported {} new command(s), merged keywords into {} existing command(s)",
+        added, merged
+    );
+
+    return Ok(());
+}
+
+/// Serializes reminders to a JSON array of
+/// `{"keywords": [...], "command": "..."}` objects.
+fn serialize_reminders_json(reminders: &Vec<Reminder>) -> String {
+    let mut json = String::from("[\n");
+
+    for (idx, reminder) in reminders.iter().enumerate() {
+        let mut keywords_vec = reminder.keywords.iter().cloned().collect::<Vec<String>>();
+        keywords_vec.sort();
+        let keywords_json = keywords_vec
+            .iter()
+            .map(|k| format!("\"{}\"", json_escape(k)))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        json.push_str(&format!(
+            "  {{\"keywords\": [{}], \"command\": \"{}\"}}",
+            keywords_json,
+            json_escape(&reminder.command)
+        ));
+        if idx + 1 < reminders.len() {
+            json.push(',');
+        }
+        json.push('\n');
+    }
+
+    json.push(']');
+    return json;
+}
+
+/// Returns true if `raw` looks like a document produced by `do_export`
+/// (a JSON array) rather than a plain reminders file.
+fn looks_like_json(raw: &str) -> bool {
+    raw.trim_start().starts_with('[')
+}
+
+/// Parses a JSON document produced by `do_export` back into reminders.
+fn parse_reminders_json(raw: &str) -> Result<Vec<Reminder>> {
+    let mut reminders = Vec::new();
+    let mut remaining = raw;
+
+    while let Some(obj_start) = remaining.find('{') {
+        let obj_end = find_matching_brace(remaining, obj_start)
+            .ok_or_else(|| Error::from("Malformed import document: unterminated object"))?;
+        let object = &remaining[obj_start..=obj_end];
+
+        let command = extract_json_string_field(object, "command")
+            .ok_or_else(|| Error::from("Malformed import document: missing 'command' field"))?;
+        let keywords = extract_json_string_array_field(object, "keywords")
+            .unwrap_or_else(Vec::new)
+            .into_iter()
+            .collect::<HashSet<String>>();
+
+        reminders.push(Reminder { keywords, command });
+        remaining = &remaining[obj_end + 1..];
+    }
+
+    return Ok(reminders);
+}
+
+/// Finds the index of the `}` that closes the object whose `{` is at
+/// `open_idx`, tracking brace depth and JSON string/escape state so that
+/// braces and brackets inside a quoted string value (e.g. a shell command
+/// like `awk '{print $1}'`) aren't mistaken for structure.
+fn find_matching_brace(s: &str, open_idx: usize) -> Option<usize> {
+    find_matching_delimiter(s, open_idx, b'{', b'}')
+}
+
+/// Same as `find_matching_brace`, but for a `[...]` array.
+fn find_matching_bracket(s: &str, open_idx: usize) -> Option<usize> {
+    find_matching_delimiter(s, open_idx, b'[', b']')
+}
+
+fn find_matching_delimiter(s: &str, open_idx: usize, open: u8, close: u8) -> Option<usize> {
+    let bytes = s.as_bytes();
+    let mut depth = 0i32;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for i in open_idx..bytes.len() {
+        let b = bytes[i];
+
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if b == b'\\' {
+                escaped = true;
+            } else if b == b'"' {
+                in_string = false;
+            }
+            continue;
+        }
+
+        if b == b'"' {
+            in_string = true;
+        } else if b == open {
+            depth += 1;
+        } else if b == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(i);
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Finds the index of the closing `"` of a JSON string value, given the
+/// index just after its opening quote. Tracks backslash escapes so an
+/// escaped quote (`\"`) inside the value doesn't end the scan early.
+fn find_json_string_end(s: &str, value_start: usize) -> Option<usize> {
+    let bytes = s.as_bytes();
+    let mut escaped = false;
+
+    for i in value_start..bytes.len() {
+        let b = bytes[i];
+        if escaped {
+            escaped = false;
+        } else if b == b'\\' {
+            escaped = true;
+        } else if b == b'"' {
+            return Some(i);
+        }
+    }
+
+    return None;
+}
+
+/// Extracts the value of a `"field": "..."` string field from a JSON object.
+fn extract_json_string_field(object: &str, field: &str) -> Option<String> {
+    let marker = format!("\"{}\"", field);
+    let after_name = object.find(&marker)? + marker.len();
+    let after_colon = object[after_name..].find(':')? + after_name + 1;
+    let value_start = object[after_colon..].find('"')? + after_colon + 1;
+    let value_end = find_json_string_end(object, value_start)?;
+    return Some(json_unescape(&object[value_start..value_end]));
+}
+
+/// Extracts the values of a `"field": ["...", ...]` string array field
+/// from a JSON object.
+fn extract_json_string_array_field(object: &str, field: &str) -> Option<Vec<String>> {
+    let marker = format!("\"{}\"", field);
+    let after_name = object.find(&marker)? + marker.len();
+    let array_start = object[after_name..].find('[')? + after_name;
+    let array_end = find_matching_bracket(object, array_start)?;
+    let inner = &object[array_start + 1..array_end];
+
+    let mut values = Vec::new();
+    let mut pos = 0;
+
+    while let Some(quote_start) = inner[pos..].find('"') {
+        let value_start = pos + quote_start + 1;
+        let value_end = find_json_string_end(inner, value_start)?;
+        values.push(json_unescape(&inner[value_start..value_end]));
+        pos = value_end + 1;
+    }
+
+    return Some(values);
+}
+
+/// Escapes backslashes and double quotes for embedding in a JSON string.
+fn json_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Reverses `json_escape`.
+fn json_unescape(s: &str) -> String {
+    s.replace("\\\"", "\"").replace("\\\\", "\\")
+}
+
+/// A single stored reminder: the set of keywords that can be used to find
+/// `command`.
+struct Reminder {
+    keywords: HashSet<String>,
+    command: String,
+}
+
+/// Splits a raw "keywords" argument into the set used by a `Reminder`.
+fn split_keywords(keywords: &str) -> HashSet<String> {
+    keywords
+        .split(' ')
+        .filter(|k| !k.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Parses the reminders file format - a "# keywords" line followed by its
+/// command line - into a list of reminders. Unlike plain line-index
+/// arithmetic, blank lines between a header and its command are skipped
+/// rather than being mispaired in as the command itself.
+fn parse_reminders(data: &str) -> Vec<Reminder> {
+    let lines = data.lines().collect::<Vec<&str>>();
+    let mut reminders = Vec::new();
+    let mut idx = 0;
+
+    while idx < lines.len() {
+        if lines[idx].starts_with('#') {
+            let mut command_idx = idx + 1;
+            while command_idx < lines.len() && lines[command_idx].trim().is_empty() {
+                command_idx += 1;
+            }
+
+            if command_idx < lines.len() {
+                let keywords = lines[idx]
+                    .split(' ')
+                    .filter(|k| *k != "#" && !k.is_empty())
+                    .map(String::from)
+                    .collect::<HashSet<String>>();
+                reminders.push(Reminder {
+                    keywords,
+                    command: lines[command_idx].to_string(),
+                });
+                idx = command_idx + 1;
+                continue;
+            }
+        }
+        idx += 1;
+    }
+
+    return reminders;
+}
+
+/// Serializes reminders back into the on-disk "# keywords" / command
+/// format read by `parse_reminders`.
+fn serialize_reminders(reminders: &Vec<Reminder>) -> String {
+    let mut data = String::new();
+
+    for reminder in reminders {
+        let mut keywords = reminder.keywords.iter().cloned().collect::<Vec<String>>();
+        keywords.sort();
+        data.push_str("# ");
+        data.push_str(&keywords.join(" "));
+        data.push('\n');
+        data.push_str(&reminder.command);
+        data.push('\n');
+    }
+
+    return data;
+}
+
+/// Reads and parses the reminders file into the structured model.
+fn read_reminders() -> Result<Vec<Reminder>> {
+    let data = read_reminders_file().chain_err(|| ErrorKind::ReadRemindersFileFailed)?;
+    return Ok(parse_reminders(&data));
+}
+
+/// Serializes reminders and overwrites the reminders file with the result.
+fn write_reminders(reminders: &Vec<Reminder>) -> Result<()> {
+    return write_reminders_file(&serialize_reminders(reminders));
+}
+
+/// Returns the indices of reminders whose keyword set contains any of the
+/// given keywords.
+fn find_matching_reminders(reminders: &Vec<Reminder>, keywords: &HashSet<String>) -> Vec<usize> {
+    return reminders
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| r.keywords.intersection(keywords).next().is_some())
+        .map(|(idx, _)| idx)
+        .collect();
+}
+
+/// Merges `new_keywords` into `reminder`'s existing keyword set.
+fn merge_keywords(reminder: &mut Reminder, new_keywords: HashSet<String>) {
+    reminder.keywords.extend(new_keywords);
+}
+
+/// Computes the path to the reminders file.
+fn reminders_file_path() -> std::path::PathBuf {
     let mut path = dirs::config_dir().unwrap();
     path.push("command-reminder");
     path.push("reminders");
+    return path;
+}
+
+/// Reads the reminders file into a string.
+fn read_reminders_file() -> Result<String> {
+    let path = reminders_file_path();
 
     // Open the file
     let mut file = OpenOptions::new()
@@ -207,10 +660,7 @@ fn read_reminders_file() -> Result<String> {
 
 /// Overwrites the reminders file with the given string.
 fn write_reminders_file(data: &str) -> Result<()> {
-    // Setup path to file
-    let mut path = dirs::config_dir().unwrap();
-    path.push("command-reminder");
-    path.push("reminders");
+    let path = reminders_file_path();
 
     let mut file = OpenOptions::new()
         .create(true)
@@ -221,61 +671,351 @@ fn write_reminders_file(data: &str) -> Result<()> {
     return writeln!(file, "{}", data).chain_err(|| "Error writing to stdout");
 }
 
-/// Merges the given keywords with any existing keywords for the given command.
-fn merge_keywords(data: &str, _command: &str, keywords: &str, command_line: usize) -> Result<()> {
-    let mut data_lines = data.lines().collect::<Vec<&str>>();
-    let keywords_str = data_lines[command_line - 1];
+/// Runs the given command via "exec", thereby replacing this processes image.
+/// Any `<name>` placeholders in `cmd` are resolved interactively first.
+fn run_command(cmd: &str) -> Result<()> {
+    let resolved = resolve_command_variables(cmd)?;
+    let cmd_args = tokenize_command(&resolved)
+        .iter()
+        .map(|s| CString::new(s.as_str()).unwrap())
+        .collect::<Vec<CString>>();
 
-    // TODO: verify syntax of keywords string
-    let new_keywords = keywords.split(' ');
-    let existing_keywords = keywords_str.split(' ');
+    if cmd_args.is_empty() {
+        return Err(ErrorKind::EmptyCommand.into());
+    }
 
-    // Collect unique keywords
-    let mut keywords_set = HashSet::<&str>::from_iter(new_keywords);
-    for keyword in existing_keywords {
-        keywords_set.insert(keyword);
+    return match nix::unistd::execvp(&cmd_args[0], &cmd_args) {
+        Ok(_) => Ok(()),
+        Err(error) => Err(error.to_string().into()),
+    };
+}
+
+/// One `<name>` / `<name:suggestion>` occurrence found while scanning a
+/// command string, paired with the exact text (including angle brackets)
+/// that produced it.
+struct PlaceholderOccurrence {
+    raw: String,
+    name: String,
+    suggestion: Option<String>,
+}
+
+/// Scans `cmd` for `<name>` and `<name:suggestion>` placeholders, a simple
+/// hand-rolled equivalent of matching `<([a-zA-Z0-9_]+)>`. Returns every
+/// occurrence in the order it appears, including repeats.
+fn scan_placeholders(cmd: &str) -> Vec<PlaceholderOccurrence> {
+    let mut occurrences = Vec::new();
+    let chars = cmd.chars().collect::<Vec<char>>();
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        if chars[idx] == '<' {
+            if let Some(offset) = chars[idx..].iter().position(|&c| c == '>') {
+                let end = idx + offset;
+                let inner = chars[idx + 1..end].iter().collect::<String>();
+                let (name, suggestion) = match inner.find(':') {
+                    Some(colon) => (
+                        inner[..colon].to_string(),
+                        Some(inner[colon + 1..].to_string()),
+                    ),
+                    None => (inner.clone(), None),
+                };
+
+                if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                    occurrences.push(PlaceholderOccurrence {
+                        raw: chars[idx..=end].iter().collect(),
+                        name,
+                        suggestion,
+                    });
+                    idx = end + 1;
+                    continue;
+                }
+            }
+        }
+        idx += 1;
     }
 
-    // Remove leading # from set - need to guarantee it is first and cant rely on set iterator ordering
-    keywords_set.remove("#");
+    return occurrences;
+}
+
+/// Replaces every `<name>` / `<name:suggestion>` placeholder in `cmd` with
+/// a value supplied by the user, prompting once per unique name in
+/// first-appearance order via the shared `read_input` helper. A placeholder
+/// with a `:suggestion` suffix runs `suggestion` as a shell command and
+/// offers its output lines as options through `ask_multiple`.
+fn resolve_command_variables(cmd: &str) -> Result<String> {
+    let occurrences = scan_placeholders(cmd);
+    let mut values: HashMap<String, String> = HashMap::new();
+
+    for occurrence in &occurrences {
+        if values.contains_key(&occurrence.name) {
+            continue;
+        }
+
+        let value = match &occurrence.suggestion {
+            Some(suggestion_cmd) => prompt_with_suggestions(&occurrence.name, suggestion_cmd)?,
+            None => read_input(&format!("{}: ", occurrence.name))
+                .chain_err(|| ErrorKind::ReadingInputFailed)?,
+        };
+        values.insert(occurrence.name.clone(), value);
+    }
 
-    // Create new keyword string
-    let mut merged_keywords = keywords_set.into_iter().collect::<Vec<&str>>().join(" ");
-    merged_keywords.insert_str(0, "# ");
-    data_lines[command_line - 1] = &merged_keywords;
+    let mut resolved = cmd.to_string();
+    for occurrence in &occurrences {
+        if let Some(value) = values.get(&occurrence.name) {
+            resolved = resolved.replace(&occurrence.raw, value);
+        }
+    }
 
-    return write_reminders_file(&data_lines.join("\n"));
+    return Ok(resolved);
 }
 
-/// Runs the given command via "exec", thereby replacing this processes image.
-fn run_command(cmd: &str) -> std::result::Result<(), std::io::Error> {
-    let cmd_parts = cmd.splitn(2, " ").collect::<Vec<&str>>();
-    let cmd_args = cmd_parts[1]
-        .split(" ")
-        .map(|s| CString::new(s).unwrap())
-        .collect::<Vec<CString>>();
+/// Runs `suggestion_cmd` through the shell and offers its output lines as
+/// selectable options for the `name` placeholder via `ask_multiple`. Falls
+/// back to a plain prompt if the suggestion command produces no output.
+fn prompt_with_suggestions(name: &str, suggestion_cmd: &str) -> Result<String> {
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(suggestion_cmd)
+        .output()
+        .chain_err(|| format!("Running suggestion command for '{}' failed", name))?;
 
-    return match nix::unistd::execvp(&CString::new(cmd_parts[0])?, &cmd_args) {
-        Ok(_) => Ok(()),
-        Err(error) => Err(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            error.to_string(),
-        )),
+    let options = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|l| l.to_string())
+        .collect::<Vec<String>>();
+
+    if options.is_empty() {
+        return read_input(&format!("{}: ", name)).chain_err(|| ErrorKind::ReadingInputFailed);
+    }
+
+    let option_refs = options.iter().map(|s| s.as_str()).collect::<Vec<&str>>();
+    let selection = ask_multiple(&option_refs).chain_err(|| ErrorKind::ReadingInputFailed)?;
+    return Ok(options[selection].clone());
+}
+
+/// Splits a command string into argv tokens, respecting single and double
+/// quotes so a substituted value containing spaces stays intact when its
+/// placeholder is quoted (e.g. `"<msg>"`); an unquoted value with spaces is
+/// split into several tokens.
+fn tokenize_command(cmd: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut quote: Option<char> = None;
+
+    for c in cmd.chars() {
+        match quote {
+            Some(q) => {
+                if c == q {
+                    quote = None;
+                } else {
+                    current.push(c);
+                }
+            }
+            None => match c {
+                '\'' | '"' => {
+                    quote = Some(c);
+                    in_token = true;
+                }
+                c if c.is_whitespace() => {
+                    if in_token {
+                        tokens.push(current.clone());
+                        current.clear();
+                        in_token = false;
+                    }
+                }
+                c => {
+                    current.push(c);
+                    in_token = true;
+                }
+            },
+        }
+    }
+
+    if in_token {
+        tokens.push(current);
+    }
+
+    return tokens;
+}
+
+/// Builds the candidate strings used for fuzzy searching, pairing each
+/// candidate with the index of the reminder it represents.
+fn build_search_candidates(reminders: &Vec<Reminder>) -> Vec<(usize, String)> {
+    let mut candidates = Vec::new();
+
+    for (idx, reminder) in reminders.iter().enumerate() {
+        let mut keywords = reminder.keywords.iter().cloned().collect::<Vec<String>>();
+        keywords.sort();
+        candidates.push((idx, format!("# {} {}", keywords.join(" "), reminder.command)));
+    }
+
+    return candidates;
+}
+
+/// Presents every stored reminder through an interactive fuzzy finder and
+/// returns the index of the reminder the user selected. Prefers shelling
+/// out to `fzf` or `skim`, falling back to an in-process subsequence
+/// matcher when neither is installed.
+fn fuzzy_search_commands(reminders: &Vec<Reminder>, keywords: &Vec<&str>) -> Result<Option<usize>> {
+    let candidates = build_search_candidates(reminders);
+    if candidates.is_empty() {
+        return Ok(None);
+    }
+
+    let query = keywords.join(" ");
+
+    match external_fuzzy_select(&candidates, &query) {
+        ExternalFuzzyResult::Selected(selected) => {
+            return Ok(candidates
+                .iter()
+                .find(|(_, candidate)| *candidate == selected)
+                .map(|(idx, _)| *idx));
+        }
+        ExternalFuzzyResult::Cancelled => return Ok(None),
+        ExternalFuzzyResult::Unavailable => {}
+    }
+
+    return builtin_fuzzy_select(&candidates, &query);
+}
+
+/// Outcome of attempting to use an external fuzzy finder for selection.
+enum ExternalFuzzyResult {
+    /// Neither `fzf` nor `sk` is installed.
+    Unavailable,
+    /// The picker ran but the user cancelled it or selected nothing.
+    Cancelled,
+    /// The user selected this candidate string.
+    Selected(String),
+}
+
+/// Attempts to use an external fuzzy finder (`fzf` or `sk`) for selection,
+/// pre-filling its query from `query`. Returns `Unavailable` only if neither
+/// binary could be spawned; a picker that ran but was aborted by the user
+/// is reported as `Cancelled`, which the caller should not treat the same
+/// as "no finder installed".
+fn external_fuzzy_select(candidates: &Vec<(usize, String)>, query: &str) -> ExternalFuzzyResult {
+    for finder in &["fzf", "sk"] {
+        let child = Command::new(finder)
+            .arg("--query")
+            .arg(query)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn();
+
+        let mut child = match child {
+            Ok(child) => child,
+            Err(_) => continue,
+        };
+
+        {
+            let stdin = child.stdin.as_mut().unwrap();
+            for (_, candidate) in candidates {
+                if writeln!(stdin, "{}", candidate).is_err() {
+                    break;
+                }
+            }
+        }
+
+        let output = match child.wait_with_output() {
+            Ok(output) => output,
+            Err(_) => return ExternalFuzzyResult::Cancelled,
+        };
+
+        if !output.status.success() {
+            return ExternalFuzzyResult::Cancelled;
+        }
+
+        let selected = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        return if selected.is_empty() {
+            ExternalFuzzyResult::Cancelled
+        } else {
+            ExternalFuzzyResult::Selected(selected)
+        };
+    }
+
+    return ExternalFuzzyResult::Unavailable;
+}
+
+/// Built-in fallback for `fuzzy_search_commands` used when neither `fzf`
+/// nor `sk` is installed: prompts for a query, scores every candidate with
+/// `fuzzy_score`, and lets the user pick among the matches via `ask_multiple`.
+fn builtin_fuzzy_select(
+    candidates: &Vec<(usize, String)>,
+    default_query: &str,
+) -> Result<Option<usize>> {
+    let response = read_input(&format!("Search [{}]: ", default_query))
+        .chain_err(|| ErrorKind::ReadingInputFailed)?;
+    let query = if response.is_empty() {
+        default_query
+    } else {
+        &response
     };
+
+    let mut scored = candidates
+        .iter()
+        .filter_map(|(idx, candidate)| {
+            fuzzy_score(candidate, query).map(|score| (score, *idx, candidate.as_str()))
+        })
+        .collect::<Vec<(i64, usize, &str)>>();
+
+    if scored.is_empty() {
+        return Ok(None);
+    }
+
+    scored.sort_by(|a, b| b.0.cmp(&a.0));
+
+    let options = scored.iter().map(|(_, _, c)| *c).collect::<Vec<&str>>();
+    let selection = ask_multiple(&options).chain_err(|| ErrorKind::ReadingInputFailed)?;
+    return Ok(Some(scored[selection].1));
 }
 
-fn find_matching_commands(data_lines: &Vec<&str>, keywords: &Vec<&str>) -> Vec<usize> {
-    let mut cmd_vec: Vec<usize> = Vec::new();
+/// Scores `candidate` against `query` using subsequence matching: the
+/// query's characters must appear in order in the candidate. Consecutive
+/// matches and matches right after a word boundary (a space or any `#`, or
+/// the very start) score higher, similar to navi's interactive picker. Returns `None`
+/// if `query` is not a subsequence of `candidate`.
+fn fuzzy_score(candidate: &str, query: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let candidate_chars = candidate.chars().collect::<Vec<char>>();
+    let query_chars = query.chars().collect::<Vec<char>>();
+
+    let mut score: i64 = 0;
+    let mut candidate_idx = 0;
+    let mut query_idx = 0;
+    let mut last_match_idx: Option<usize> = None;
 
-    // Collect commands that have matching keywords
-    for idx in 0..data_lines.len() {
-        if data_lines[idx].starts_with("#") && keywords.iter().any(|k| data_lines[idx].contains(k))
+    while candidate_idx < candidate_chars.len() && query_idx < query_chars.len() {
+        if candidate_chars[candidate_idx].to_ascii_lowercase()
+            == query_chars[query_idx].to_ascii_lowercase()
         {
-            cmd_vec.push(idx + 1);
+            let is_boundary = candidate_idx == 0
+                || candidate_chars[candidate_idx - 1] == ' '
+                || candidate_chars[candidate_idx - 1] == '#';
+            let is_consecutive = last_match_idx.map_or(false, |i| i + 1 == candidate_idx);
+
+            score += 1;
+            if is_boundary {
+                score += 5;
+            }
+            if is_consecutive {
+                score += 3;
+            }
+
+            last_match_idx = Some(candidate_idx);
+            query_idx += 1;
         }
+        candidate_idx += 1;
     }
 
-    return cmd_vec;
+    return if query_idx == query_chars.len() {
+        Some(score)
+    } else {
+        None
+    };
 }
 
 /// Asks the user to select from one of the given options and returns
@@ -310,6 +1050,21 @@ fn ask_multiple(options: &Vec<&str>) -> std::result::Result<usize, std::io::Erro
     }
 }
 
+/// Prints `prompt` and returns the user's trimmed response. Shared by any
+/// code that needs a single line of free-form input from the user.
+fn read_input(prompt: &str) -> Result<String> {
+    print!("{}", prompt);
+    std::io::stdout()
+        .flush()
+        .chain_err(|| ErrorKind::ReadingInputFailed)?;
+
+    let mut response = String::new();
+    std::io::stdin()
+        .read_line(&mut response)
+        .chain_err(|| ErrorKind::ReadingInputFailed)?;
+    return Ok(response.trim().to_string());
+}
+
 /// Asks a yes/no question of the user. Returns true for yes and false for no.
 /// If the user gives an unexpected answer, the question is asked again.
 fn ask_yes_no(question: &str) -> std::result::Result<bool, std::io::Error> {